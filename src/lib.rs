@@ -25,9 +25,11 @@
 
 
 use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
 use std::slice;
 use std::iter::{Extend, FromIterator};
-use std::ops::Index;
+use std::ops::{Index, IndexMut};
 use std::vec;
 
 
@@ -382,43 +384,1003 @@ impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
 
 	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
 	///
+	/// This is an alias for [`swap_remove_by_first()`](#method.swap_remove_by_first) kept around for backwards
+	/// compatibility; like it, it doesn't preserve the relative order of the remaining pairs -- use
+	/// [`shift_remove_by_first()`](#method.shift_remove_by_first) if that matters.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.remove_by_first(&1), Some((1, "a")));
+	/// assert_eq!(map.remove_by_first(&1), None);
+	/// ```
+	pub fn remove_by_first<Q: ?Sized>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq<Kv1>,
+	{
+		self.swap_remove_by_first(key)
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, returning it if the key was previously in the map.
+	///
+	/// This is an alias for [`swap_remove_by_second()`](#method.swap_remove_by_second) kept around for backwards
+	/// compatibility; like it, it doesn't preserve the relative order of the remaining pairs -- use
+	/// [`shift_remove_by_second()`](#method.shift_remove_by_second) if that matters.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.remove_by_second(&"a"), Some((1, "a")));
+	/// assert_eq!(map.remove_by_second(&"b"), None);
+	/// ```
+	pub fn remove_by_second<Q: ?Sized>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq<Kv2>,
+	{
+		self.swap_remove_by_second(key)
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map via `Vec::swap_remove()`, returning it if the
+	/// key was previously in the map.
+	///
+	/// This runs in `O(n)` but doesn't preserve the relative order of the remaining pairs, as the last pair is
+	/// moved into the removed slot. See [`shift_remove_by_first()`](#method.shift_remove_by_first) for the
+	/// order-preserving equivalent.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// map.insert(3, "c");
+	/// assert_eq!(map.swap_remove_by_first(&1), Some((1, "a")));
+	/// assert_eq!(map.first_col().cloned().collect::<Vec<_>>(), [3, 2]);
+	/// ```
+	pub fn swap_remove_by_first<Q: ?Sized>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq<Kv1>,
+	{
+		self.cont.iter().position(|ref kvs| *key == kvs.0).map(|idx| self.cont.swap_remove(idx))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map via `Vec::swap_remove()`, returning it if the
+	/// key was previously in the map.
+	///
+	/// This runs in `O(n)` but doesn't preserve the relative order of the remaining pairs, as the last pair is
+	/// moved into the removed slot. See [`shift_remove_by_second()`](#method.shift_remove_by_second) for the
+	/// order-preserving equivalent.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// map.insert(3, "c");
+	/// assert_eq!(map.swap_remove_by_second(&"a"), Some((1, "a")));
+	/// assert_eq!(map.first_col().cloned().collect::<Vec<_>>(), [3, 2]);
+	/// ```
+	pub fn swap_remove_by_second<Q: ?Sized>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq<Kv2>,
+	{
+		self.cont.iter().position(|ref kvs| *key == kvs.1).map(|idx| self.cont.swap_remove(idx))
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map via `Vec::remove()`, returning it if the key
+	/// was previously in the map.
+	///
+	/// This preserves the relative order of the remaining pairs, at the cost of an `O(n)` shift of everything
+	/// after the removed slot. See [`swap_remove_by_first()`](#method.swap_remove_by_first) if order doesn't matter.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// map.insert(3, "c");
+	/// assert_eq!(map.shift_remove_by_first(&1), Some((1, "a")));
+	/// assert_eq!(map.first_col().cloned().collect::<Vec<_>>(), [2, 3]);
+	/// ```
+	pub fn shift_remove_by_first<Q: ?Sized>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq<Kv1>,
+	{
+		self.cont.iter().position(|ref kvs| *key == kvs.0).map(|idx| self.cont.remove(idx))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map via `Vec::remove()`, returning it if the key
+	/// was previously in the map.
+	///
+	/// This preserves the relative order of the remaining pairs, at the cost of an `O(n)` shift of everything
+	/// after the removed slot. See [`swap_remove_by_second()`](#method.swap_remove_by_second) if order doesn't matter.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// map.insert(3, "c");
+	/// assert_eq!(map.shift_remove_by_second(&"a"), Some((1, "a")));
+	/// assert_eq!(map.first_col().cloned().collect::<Vec<_>>(), [2, 3]);
+	/// ```
+	pub fn shift_remove_by_second<Q: ?Sized>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq<Kv2>,
+	{
+		self.cont.iter().position(|ref kvs| *key == kvs.1).map(|idx| self.cont.remove(idx))
+	}
+
+	/// Gets the K/V-K/V pair at the given positional index, treating the map as an ordered sequence.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// assert_eq!(map.get_index(0), Some((&1, &"a")));
+	/// assert_eq!(map.get_index(2), None);
+	/// ```
+	pub fn get_index(&self, n: usize) -> Option<(&Kv1, &Kv2)> {
+		self.cont.get(n).map(|kvs| (&kvs.0, &kvs.1))
+	}
+
+	/// Gets a mutable reference to the K/V-K/V pair at the given positional index.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// if let Some((_, second)) = map.get_index_mut(0) {
+	/// 	*second = "z";
+	/// }
+	/// assert_eq!(map.get_by_first(&1), Some(&"z"));
+	/// ```
+	pub fn get_index_mut(&mut self, n: usize) -> Option<(&mut Kv1, &mut Kv2)> {
+		self.cont.get_mut(n).map(|kvs| (&mut kvs.0, &mut kvs.1))
+	}
+
+	/// Swaps the positions of the pairs at indices `a` and `b`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// map.swap_indices(0, 1);
+	/// assert_eq!(map.first_col().cloned().collect::<Vec<_>>(), [2, 1]);
+	/// ```
+	pub fn swap_indices(&mut self, a: usize, b: usize) {
+		self.cont.swap(a, b)
+	}
+
+	/// Retains only the pairs for which `f` returns `true`, removing the rest in a single pass.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// map.insert(3, "c");
+	/// map.retain(|first, _| *first != 2);
+	/// assert_eq!(map.first_col().cloned().collect::<Vec<_>>(), [1, 3]);
+	/// ```
+	pub fn retain<F: FnMut(&Kv1, &Kv2) -> bool>(&mut self, mut f: F) {
+		self.cont.retain(|kvs| f(&kvs.0, &kvs.1))
+	}
+
+	/// Sorts the pairs with a comparator function over both K/Vs, as `Vec::sort_by()` would.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(2, "b");
+	/// map.insert(1, "a");
+	/// map.sort_by(|a, b| a.0.cmp(&b.0));
+	/// assert_eq!(map.first_col().cloned().collect::<Vec<_>>(), [1, 2]);
+	/// ```
+	pub fn sort_by<F: FnMut(&(Kv1, Kv2), &(Kv1, Kv2)) -> ::std::cmp::Ordering>(&mut self, f: F) {
+		self.cont.sort_by(f)
+	}
+
+	/// Gets the given first K/V's corresponding entry in the map for in-place manipulation.
+	///
+	/// Unlike `insert()`, this doesn't silently drop a pre-existing pair that happens to own the second K/V the
+	/// caller wants to pair the entry with -- see [`EntryByFirst::or_insert()`](enum.EntryByFirst.html#method.or_insert).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, EntryByFirst};
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	///
+	/// if let EntryByFirst::Vacant(entry) = map.entry_by_first(2) {
+	/// 	entry.or_insert("b").unwrap();
+	/// }
+	/// assert_eq!(map.get_by_first(&2), Some(&"b"));
+	/// ```
+	pub fn entry_by_first(&mut self, first: Kv1) -> EntryByFirst<Kv1, Kv2> {
+		match self.cont.iter().position(|kvs| kvs.0 == first) {
+			Some(index) => EntryByFirst::Occupied(OccupiedEntryByFirst{ map: self, index: index }),
+			None => EntryByFirst::Vacant(VacantEntryByFirst{ map: self, first: first }),
+		}
+	}
+
+	/// Gets the given second K/V's corresponding entry in the map for in-place manipulation.
+	///
+	/// Unlike `insert()`, this doesn't silently drop a pre-existing pair that happens to own the first K/V the
+	/// caller wants to pair the entry with -- see [`EntryBySecond::or_insert()`](enum.EntryBySecond.html#method.or_insert).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, EntryBySecond};
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	///
+	/// if let EntryBySecond::Vacant(entry) = map.entry_by_second("b") {
+	/// 	entry.or_insert(2).unwrap();
+	/// }
+	/// assert_eq!(map.get_by_second(&"b"), Some(&2));
+	/// ```
+	pub fn entry_by_second(&mut self, second: Kv2) -> EntryBySecond<Kv1, Kv2> {
+		match self.cont.iter().position(|kvs| kvs.1 == second) {
+			Some(index) => EntryBySecond::Occupied(OccupiedEntryBySecond{ map: self, index: index }),
+			None => EntryBySecond::Vacant(VacantEntryBySecond{ map: self, second: second }),
+		}
+	}
+}
+
+impl<Kv1: PartialEq + Ord, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Sorts the pairs by their first K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(2, "b");
+	/// map.insert(1, "a");
+	/// map.sort_by_first();
+	/// assert_eq!(map.first_col().cloned().collect::<Vec<_>>(), [1, 2]);
+	/// ```
+	pub fn sort_by_first(&mut self) {
+		self.cont.sort_by(|a, b| a.0.cmp(&b.0))
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq + Ord> BidirMap<Kv1, Kv2> {
+	/// Sorts the pairs by their second K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "b");
+	/// map.insert(2, "a");
+	/// map.sort_by_second();
+	/// assert_eq!(map.second_col().cloned().collect::<Vec<_>>(), ["a", "b"]);
+	/// ```
+	pub fn sort_by_second(&mut self) {
+		self.cont.sort_by(|a, b| a.1.cmp(&b.1))
+	}
+}
+
+impl<Kv1: PartialEq + Eq + Hash, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Computes the changes needed to turn `self` into `other`, keyed on the first K/V.
+	///
+	/// Since `cont` is an unordered `Vec`, this builds a temporary lookup from `other`'s first K/Vs to their
+	/// second K/Vs, then walks `self` classifying each pair as [`Removed`](enum.DiffItem.html#variant.Removed)
+	/// (its first K/V is gone from `other`) or [`Update`](enum.DiffItem.html#variant.Update) (its first K/V is
+	/// still there, but paired with a different second K/V); whatever's left unclaimed in the lookup once `self`
+	/// has been walked is [`Added`](enum.DiffItem.html#variant.Added) in `other`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, DiffItem, bidir_map};
+	///
+	/// let a = bidir_map!(1 => "a", 2 => "b", 3 => "c");
+	/// let b = bidir_map!(1 => "a", 2 => "bb", 4 => "d");
+	///
+	/// let mut diff: Vec<_> = a.diff(&b).collect();
+	/// diff.sort_by_key(|item| match *item {
+	/// 	DiffItem::Added(first, _)   => *first,
+	/// 	DiffItem::Removed(first, _) => *first,
+	/// 	DiffItem::Update{first, ..} => *first,
+	/// });
+	///
+	/// assert_eq!(diff, vec![
+	/// 	DiffItem::Update{first: &2, old_second: &"b", new_second: &"bb"},
+	/// 	DiffItem::Removed(&3, &"c"),
+	/// 	DiffItem::Added(&4, &"d"),
+	/// ]);
+	/// ```
+	pub fn diff<'a>(&'a self, other: &'a BidirMap<Kv1, Kv2>) -> Diff<'a, Kv1, Kv2> {
+		let mut other_by_first: HashMap<&Kv1, &Kv2> = HashMap::with_capacity(other.cont.len());
+		for &(ref first, ref second) in &other.cont {
+			other_by_first.insert(first, second);
+		}
+
+		let mut items = Vec::new();
+		for &(ref first, ref second) in &self.cont {
+			match other_by_first.remove(first) {
+				Some(other_second) =>
+					if other_second != second {
+						items.push(DiffItem::Update{ first: first, old_second: second, new_second: other_second });
+					},
+				None => items.push(DiffItem::Removed(first, second)),
+			}
+		}
+		for (first, second) in other_by_first {
+			items.push(DiffItem::Added(first, second));
+		}
+
+		Diff{ items: items.into_iter() }
+	}
+}
+
+
+/// A change record yielded by [`BidirMap::diff()`](struct.BidirMap.html#method.diff).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffItem<'a, Kv1: 'a, Kv2: 'a> {
+	/// The first K/V, absent from `self`, is present in `other`, paired with the given second K/V.
+	Added(&'a Kv1, &'a Kv2),
+	/// The first K/V, present in `self`, is absent from `other`.
+	Removed(&'a Kv1, &'a Kv2),
+	/// The first K/V is present in both maps, but paired with a different second K/V in `other`.
+	Update {
+		/// The (unchanged) first K/V.
+		first: &'a Kv1,
+		/// The second K/V it was paired with in `self`.
+		old_second: &'a Kv2,
+		/// The second K/V it's paired with in `other`.
+		new_second: &'a Kv2,
+	},
+}
+
+/// An iterator over the changes needed to turn one `BidirMap` into another.
+///
+/// See documentation of [`BidirMap::diff()`](struct.BidirMap.html#method.diff) for more.
+pub struct Diff<'a, Kv1: 'a, Kv2: 'a> {
+	items: vec::IntoIter<DiffItem<'a, Kv1, Kv2>>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for Diff<'a, Kv1, Kv2> {
+	type Item = DiffItem<'a, Kv1, Kv2>;
+	fn next(&mut self) -> Option<Self::Item> {
+		self.items.next()
+	}
+}
+
+
+/// A view into a single first-K/V entry in a `BidirMap`, obtained from [`BidirMap::entry_by_first()`](struct.BidirMap.html#method.entry_by_first).
+pub enum EntryByFirst<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> {
+	/// The first K/V is present; it maps to the contained second K/V.
+	Occupied(OccupiedEntryByFirst<'a, Kv1, Kv2>),
+	/// The first K/V is absent.
+	Vacant(VacantEntryByFirst<'a, Kv1, Kv2>),
+}
+
+impl<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> EntryByFirst<'a, Kv1, Kv2> {
+	/// Ensures the entry is occupied by pairing the first K/V with `second`, inserting it if vacant, and returns a
+	/// mutable reference to the resulting second K/V.
+	///
+	/// Because the map is bidirectional, `second` might already belong to an unrelated pair; in that case, no
+	/// insertion happens and [`VacantButSecondOccupied`](struct.VacantButSecondOccupied.html) is returned with both
+	/// halves the caller tried to insert, plus a reference to the first K/V of the pair already holding `second`,
+	/// so the collision can be resolved deliberately rather than the old pair being silently dropped.
+	pub fn or_insert(self, second: Kv2) -> Result<&'a mut Kv2, VacantButSecondOccupied<'a, Kv1, Kv2>> {
+		match self {
+			EntryByFirst::Occupied(entry) => Ok(entry.into_mut()),
+			EntryByFirst::Vacant(entry) => entry.or_insert(second),
+		}
+	}
+
+	/// Provides in-place mutable access to an occupied entry's second K/V before any potential insertion.
+	///
+	/// Only the second K/V is exposed, not the first: the first K/V is the key this entry was looked up by, and
+	/// mutating it in place -- same as `OccupiedEntryByFirst`, which exposes `second_mut()`/`into_mut()` but no
+	/// `first_mut()` -- would desync it from the rest of `cont` without updating `contains_first_key()`'s view of
+	/// the map, silently duplicating a first K/V and making one of the two entries unreachable by key.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	///
+	/// map.entry_by_first(1).and_modify(|second| *second = "b");
+	/// assert_eq!(map.get_by_first(&1), Some(&"b"));
+	/// ```
+	pub fn and_modify<F: FnOnce(&mut Kv2)>(mut self, f: F) -> Self {
+		if let EntryByFirst::Occupied(ref mut entry) = self {
+			f(&mut entry.map.cont[entry.index].1);
+		}
+		self
+	}
+}
+
+/// An occupied entry from an [`EntryByFirst`](enum.EntryByFirst.html).
+pub struct OccupiedEntryByFirst<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> {
+	map: &'a mut BidirMap<Kv1, Kv2>,
+	index: usize,
+}
+
+impl<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> OccupiedEntryByFirst<'a, Kv1, Kv2> {
+	/// Gets a reference to the first K/V in the entry.
+	pub fn first(&self) -> &Kv1 {
+		&self.map.cont[self.index].0
+	}
+
+	/// Gets a reference to the second K/V in the entry.
+	pub fn second(&self) -> &Kv2 {
+		&self.map.cont[self.index].1
+	}
+
+	/// Gets a mutable reference to the second K/V in the entry.
+	pub fn second_mut(&mut self) -> &mut Kv2 {
+		&mut self.map.cont[self.index].1
+	}
+
+	/// Converts the entry into a mutable reference to the second K/V, tied to the map's lifetime.
+	pub fn into_mut(self) -> &'a mut Kv2 {
+		&mut self.map.cont[self.index].1
+	}
+
+	/// Removes the pair from the map, returning it.
+	pub fn remove(self) -> (Kv1, Kv2) {
+		self.map.cont.swap_remove(self.index)
+	}
+}
+
+/// A vacant entry from an [`EntryByFirst`](enum.EntryByFirst.html).
+pub struct VacantEntryByFirst<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> {
+	map: &'a mut BidirMap<Kv1, Kv2>,
+	first: Kv1,
+}
+
+impl<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> VacantEntryByFirst<'a, Kv1, Kv2> {
+	/// Gets a reference to the first K/V that will be used if the entry is inserted.
+	pub fn first(&self) -> &Kv1 {
+		&self.first
+	}
+
+	/// Pairs the vacant first K/V with `second` and inserts it into the map, returning a mutable reference to the
+	/// second K/V, unless `second` already belongs to another pair, in which case neither half is inserted.
+	pub fn or_insert(self, second: Kv2) -> Result<&'a mut Kv2, VacantButSecondOccupied<'a, Kv1, Kv2>> {
+		match self.map.cont.iter().position(|kvs| kvs.1 == second) {
+			Some(existing) =>
+				Err(VacantButSecondOccupied{
+					first: self.first,
+					second: second,
+					existing_first: &self.map.cont[existing].0,
+				}),
+			None => {
+				self.map.cont.push((self.first, second));
+				let index = self.map.cont.len() - 1;
+				Ok(&mut self.map.cont[index].1)
+			},
+		}
+	}
+}
+
+
+/// A view into a single second-K/V entry in a `BidirMap`, obtained from [`BidirMap::entry_by_second()`](struct.BidirMap.html#method.entry_by_second).
+pub enum EntryBySecond<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> {
+	/// The second K/V is present; it maps to the contained first K/V.
+	Occupied(OccupiedEntryBySecond<'a, Kv1, Kv2>),
+	/// The second K/V is absent.
+	Vacant(VacantEntryBySecond<'a, Kv1, Kv2>),
+}
+
+impl<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> EntryBySecond<'a, Kv1, Kv2> {
+	/// Ensures the entry is occupied by pairing the second K/V with `first`, inserting it if vacant, and returns a
+	/// mutable reference to the resulting first K/V.
+	///
+	/// Because the map is bidirectional, `first` might already belong to an unrelated pair; in that case, no
+	/// insertion happens and [`VacantButFirstOccupied`](struct.VacantButFirstOccupied.html) is returned with both
+	/// halves the caller tried to insert, plus a reference to the second K/V of the pair already holding `first`,
+	/// so the collision can be resolved deliberately rather than the old pair being silently dropped.
+	pub fn or_insert(self, first: Kv1) -> Result<&'a mut Kv1, VacantButFirstOccupied<'a, Kv1, Kv2>> {
+		match self {
+			EntryBySecond::Occupied(entry) => Ok(entry.into_mut()),
+			EntryBySecond::Vacant(entry) => entry.or_insert(first),
+		}
+	}
+
+	/// Provides in-place mutable access to an occupied entry's first K/V before any potential insertion.
+	///
+	/// Only the first K/V is exposed, not the second: the second K/V is the key this entry was looked up by, and
+	/// mutating it in place -- same as `OccupiedEntryBySecond`, which exposes `first_mut()`/`into_mut()` but no
+	/// `second_mut()` -- would desync it from the rest of `cont` without updating `contains_second_key()`'s view
+	/// of the map, silently duplicating a second K/V and making one of the two entries unreachable by key.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	///
+	/// map.entry_by_second("a").and_modify(|first| *first = 2);
+	/// assert_eq!(map.get_by_second(&"a"), Some(&2));
+	/// ```
+	pub fn and_modify<F: FnOnce(&mut Kv1)>(mut self, f: F) -> Self {
+		if let EntryBySecond::Occupied(ref mut entry) = self {
+			f(&mut entry.map.cont[entry.index].0);
+		}
+		self
+	}
+}
+
+/// An occupied entry from an [`EntryBySecond`](enum.EntryBySecond.html).
+pub struct OccupiedEntryBySecond<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> {
+	map: &'a mut BidirMap<Kv1, Kv2>,
+	index: usize,
+}
+
+impl<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> OccupiedEntryBySecond<'a, Kv1, Kv2> {
+	/// Gets a reference to the first K/V in the entry.
+	pub fn first(&self) -> &Kv1 {
+		&self.map.cont[self.index].0
+	}
+
+	/// Gets a reference to the second K/V in the entry.
+	pub fn second(&self) -> &Kv2 {
+		&self.map.cont[self.index].1
+	}
+
+	/// Gets a mutable reference to the first K/V in the entry.
+	pub fn first_mut(&mut self) -> &mut Kv1 {
+		&mut self.map.cont[self.index].0
+	}
+
+	/// Converts the entry into a mutable reference to the first K/V, tied to the map's lifetime.
+	pub fn into_mut(self) -> &'a mut Kv1 {
+		&mut self.map.cont[self.index].0
+	}
+
+	/// Removes the pair from the map, returning it.
+	pub fn remove(self) -> (Kv1, Kv2) {
+		self.map.cont.swap_remove(self.index)
+	}
+}
+
+/// A vacant entry from an [`EntryBySecond`](enum.EntryBySecond.html).
+pub struct VacantEntryBySecond<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> {
+	map: &'a mut BidirMap<Kv1, Kv2>,
+	second: Kv2,
+}
+
+impl<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> VacantEntryBySecond<'a, Kv1, Kv2> {
+	/// Gets a reference to the second K/V that will be used if the entry is inserted.
+	pub fn second(&self) -> &Kv2 {
+		&self.second
+	}
+
+	/// Pairs the vacant second K/V with `first` and inserts it into the map, returning a mutable reference to the
+	/// first K/V, unless `first` already belongs to another pair, in which case neither half is inserted.
+	pub fn or_insert(self, first: Kv1) -> Result<&'a mut Kv1, VacantButFirstOccupied<'a, Kv1, Kv2>> {
+		match self.map.cont.iter().position(|kvs| kvs.0 == first) {
+			Some(existing) =>
+				Err(VacantButFirstOccupied{
+					first: first,
+					second: self.second,
+					existing_second: &self.map.cont[existing].1,
+				}),
+			None => {
+				self.map.cont.push((first, self.second));
+				let index = self.map.cont.len() - 1;
+				Ok(&mut self.map.cont[index].0)
+			},
+		}
+	}
+}
+
+
+/// Returned from [`EntryByFirst::or_insert()`](enum.EntryByFirst.html#method.or_insert) when inserting would
+/// silently clobber an unrelated pair that already owns the requested second K/V.
+///
+/// Bundles back both halves the caller tried to insert, plus a reference to the first K/V of the pair already
+/// holding `second`, so the collision can be inspected and resolved instead of being silently resolved for you.
+#[derive(Debug)]
+pub struct VacantButSecondOccupied<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> {
+	/// The first K/V the caller tried to insert.
+	pub first: Kv1,
+	/// The second K/V the caller tried to pair it with.
+	pub second: Kv2,
+	/// The first K/V of the pre-existing pair that already owns `second`.
+	pub existing_first: &'a Kv1,
+}
+
+/// Returned from [`EntryBySecond::or_insert()`](enum.EntryBySecond.html#method.or_insert) when inserting would
+/// silently clobber an unrelated pair that already owns the requested first K/V.
+///
+/// Bundles back both halves the caller tried to insert, plus a reference to the second K/V of the pair already
+/// holding `first`, so the collision can be inspected and resolved instead of being silently resolved for you.
+#[derive(Debug)]
+pub struct VacantButFirstOccupied<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> {
+	/// The first K/V the caller tried to pair `second` with.
+	pub first: Kv1,
+	/// The second K/V the caller tried to insert.
+	pub second: Kv2,
+	/// The second K/V of the pre-existing pair that already owns `first`.
+	pub existing_second: &'a Kv2,
+}
+
+
+/// A bidirectional map with `O(log n)` lookups, at the cost of requiring `Ord` keys on both sides.
+///
+/// Like `BidirMap`, the K/V pairs are stored in an owned `Vec`, but two auxiliary `Vec<usize>`
+/// permutation arrays are kept alongside it: `by_first`, sorted by the first column, and `by_second`,
+/// sorted by the second. `get_by_first`/`get_by_second`/`contains_*`/`remove_by_*` binary-search the
+/// relevant index array to find the storage slot, rather than scanning `cont` linearly.
+///
+/// Both index arrays are always permutations of `0..cont.len()`, kept in sorted order by their
+/// respective column; maintaining this invariant costs `2 * size_of::<usize>()` of extra memory per
+/// entry over a plain `BidirMap`.
+///
+/// Performance: `O(log n)` for lookups and membership checks; `O(n)` for insertion and removal,
+/// from shifting the index arrays (and, on removal, scanning them for the slot moved by `swap_remove()`),
+/// while K/V comparisons stay `O(log n)`.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct SortedBidirMap<Kv1: Ord, Kv2: Ord> {
+	cont: Vec<(Kv1, Kv2)>,
+	by_first: Vec<usize>,
+	by_second: Vec<usize>,
+}
+
+impl<Kv1: Ord, Kv2: Ord> SortedBidirMap<Kv1, Kv2> {
+	/// Create a new empty instance of `SortedBidirMap`
+	pub fn new() -> Self {
+		SortedBidirMap{
+			cont: Vec::new(),
+			by_first: Vec::new(),
+			by_second: Vec::new(),
+		}
+	}
+
+	/// Create a new empty instance of `SortedBidirMap` with the specified capacity.
+	///
+	/// It will be able to hold at least `capacity` elements without reallocating.
+	pub fn with_capacity(capacity: usize) -> Self {
+		SortedBidirMap{
+			cont: Vec::with_capacity(capacity),
+			by_first: Vec::with_capacity(capacity),
+			by_second: Vec::with_capacity(capacity),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.cont.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.cont.is_empty()
+	}
+
+	/// Gets an iterator over the entries of the map, in unspecified (storage) order.
+	///
 	/// # Examples
 	///
 	/// ```
-	/// use bidir_map::BidirMap;
+	/// use bidir_map::SortedBidirMap;
 	///
-	/// let mut map = BidirMap::new();
+	/// let mut map = SortedBidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	///
+	/// let mut pairs: Vec<_> = map.iter().collect();
+	/// pairs.sort();
+	/// assert_eq!(pairs, [(&1, &"a"), (&2, &"b")]);
+	/// ```
+	pub fn iter(&self) -> Iter<Kv1, Kv2> {
+		Iter{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Gets an iterator over the first K/V of the map, in unspecified (storage) order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::SortedBidirMap;
+	///
+	/// let mut map = SortedBidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	///
+	/// let mut keys: Vec<_> = map.first_col().cloned().collect();
+	/// keys.sort();
+	/// assert_eq!(keys, [1, 2]);
+	/// ```
+	pub fn first_col(&self) -> FirstColumn<Kv1, Kv2> {
+		FirstColumn{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Gets an iterator over the second K/V of the map, in unspecified (storage) order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::SortedBidirMap;
+	///
+	/// let mut map = SortedBidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	///
+	/// let mut keys: Vec<_> = map.second_col().cloned().collect();
+	/// keys.sort();
+	/// assert_eq!(keys, ["a", "b"]);
+	/// ```
+	pub fn second_col(&self) -> SecondColumn<Kv1, Kv2> {
+		SecondColumn{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Inserts a K/V-K/V pair into the map.
+	///
+	/// If the map did not have this K/V-K/V pair present, `None` is returned.
+	///
+	/// If the map did have this K/V-K/V pair present, it's updated and the old K/V-K/V pair is returned.
+	///
+	/// Unlike `BidirMap::insert()`'s `if contains_first_key() { .. } else if contains_second_key() { .. }`, both
+	/// the first *and* the second K/V are independently checked for collisions and, if present, removed: `kv1` may
+	/// collide with one pre-existing pair while `kv2` collides with a *different* one, and leaving either behind
+	/// would plant a duplicate value in `by_first` or `by_second`, corrupting the sortedness invariant every
+	/// binary search here relies on.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::SortedBidirMap;
+	///
+	/// let mut map = SortedBidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// assert_eq!(map.get_by_first(&1), Some(&"a"));
+	/// assert_eq!(map.get_by_second(&"b"), Some(&2));
+	///
+	/// // kv1 collides with one pair, kv2 with another -- both are evicted.
+	/// assert_eq!(map.insert(1, "b"), Some((1, "a")));
+	/// assert_eq!(map.len(), 1);
+	/// assert_eq!(map.get_by_first(&1), Some(&"b"));
+	/// assert_eq!(map.get_by_first(&2), None);
+	/// ```
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let by_first_collision  = self.remove_by_first(&kv1);
+		let by_second_collision = self.remove_by_second(&kv2);
+
+		let first_pos  = self.by_first.binary_search_by(|&i| self.cont[i].0.cmp(&kv1)).unwrap_or_else(|pos| pos);
+		let second_pos = self.by_second.binary_search_by(|&i| self.cont[i].1.cmp(&kv2)).unwrap_or_else(|pos| pos);
+		let slot = self.cont.len();
+
+		self.cont.push((kv1, kv2));
+		self.by_first.insert(first_pos, slot);
+		self.by_second.insert(second_pos, slot);
+
+		by_first_collision.or(by_second_collision)
+	}
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::SortedBidirMap;
+	///
+	/// let mut map = SortedBidirMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.get_by_first(&1), Some(&"a"));
+	/// assert_eq!(map.get_by_first(&2), None);
+	/// ```
+	pub fn get_by_first<Q: ?Sized + Ord>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+	{
+		self.by_first.binary_search_by(|&i| self.cont[i].0.borrow().cmp(key)).ok().map(|pos| &self.cont[self.by_first[pos]].1)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::SortedBidirMap;
+	///
+	/// let mut map = SortedBidirMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.get_by_second(&"a"), Some(&1));
+	/// assert_eq!(map.get_by_second(&"b"), None);
+	/// ```
+	pub fn get_by_second<Q: ?Sized + Ord>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+	{
+		self.by_second.binary_search_by(|&i| self.cont[i].1.borrow().cmp(key)).ok().map(|pos| &self.cont[self.by_second[pos]].0)
+	}
+
+	/// Returns a mutable reference to the second K/V corresponding to the first K/V.
+	///
+	/// Mutating the returned value does *not* re-sort `by_second`: since the second column is itself a sort key,
+	/// changing the returned value such that the second-column ordering or uniqueness breaks will corrupt later
+	/// `get_by_second()`/`contains_second_key()`/`remove_by_second()` lookups. Prefer `remove_by_first()` followed
+	/// by `insert()` if the replacement value might collide with, or reorder against, another entry.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::SortedBidirMap;
+	///
+	/// let mut map = SortedBidirMap::new();
+	/// map.insert(1, "a");
+	/// if let Some(x) = map.get_mut_by_first(&1) {
+	///     *x = "b";
+	/// }
+	/// assert_eq!(map.get_by_first(&1), Some(&"b"));
+	/// ```
+	pub fn get_mut_by_first<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<&mut Kv2>
+		where Kv1: Borrow<Q>,
+	{
+		match self.by_first.binary_search_by(|&i| self.cont[i].0.borrow().cmp(key)) {
+			Ok(pos) => Some(&mut self.cont[self.by_first[pos]].1),
+			Err(_) => None,
+		}
+	}
+
+	/// Returns a mutable reference to the first K/V corresponding to the second K/V.
+	///
+	/// Mutating the returned value does *not* re-sort `by_first`: since the first column is itself a sort key,
+	/// changing the returned value such that the first-column ordering or uniqueness breaks will corrupt later
+	/// `get_by_first()`/`contains_first_key()`/`remove_by_first()` lookups. Prefer `remove_by_second()` followed
+	/// by `insert()` if the replacement value might collide with, or reorder against, another entry.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::SortedBidirMap;
+	///
+	/// let mut map = SortedBidirMap::new();
+	/// map.insert(1, "a");
+	/// if let Some(x) = map.get_mut_by_second(&"a") {
+	///     *x = 2;
+	/// }
+	/// assert_eq!(map.get_by_second(&"a"), Some(&2));
+	/// ```
+	pub fn get_mut_by_second<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<&mut Kv1>
+		where Kv2: Borrow<Q>,
+	{
+		match self.by_second.binary_search_by(|&i| self.cont[i].1.borrow().cmp(key)) {
+			Ok(pos) => Some(&mut self.cont[self.by_second[pos]].0),
+			Err(_) => None,
+		}
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_first_key<Q: ?Sized + Ord>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+	{
+		self.by_first.binary_search_by(|&i| self.cont[i].0.borrow().cmp(key)).is_ok()
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_second_key<Q: ?Sized + Ord>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+	{
+		self.by_second.binary_search_by(|&i| self.cont[i].1.borrow().cmp(key)).is_ok()
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::SortedBidirMap;
+	///
+	/// let mut map = SortedBidirMap::new();
 	/// map.insert(1, "a");
 	/// assert_eq!(map.remove_by_first(&1), Some((1, "a")));
 	/// assert_eq!(map.remove_by_first(&1), None);
 	/// ```
-	pub fn remove_by_first<Q: ?Sized>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+	pub fn remove_by_first<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
 		where Kv1: Borrow<Q>,
-		      Q  : PartialEq<Kv1>,
 	{
-		self.cont.iter().position(|ref kvs| *key == kvs.0).map(|idx| self.cont.swap_remove(idx))
+		let first_pos = self.by_first.binary_search_by(|&i| self.cont[i].0.borrow().cmp(key)).ok()?;
+		let slot = self.by_first.remove(first_pos);
+		let second_pos = self.by_second.binary_search_by(|&i| self.cont[i].1.cmp(&self.cont[slot].1)).expect("by_second out of sync with by_first");
+		self.by_second.remove(second_pos);
+		Some(self.finish_remove(slot))
 	}
 
-	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	/// Removes the pair corresponding to the second K/V from the map, returning it if the key was previously in the map.
 	///
 	/// # Examples
 	///
 	/// ```
-	/// use bidir_map::BidirMap;
+	/// use bidir_map::SortedBidirMap;
 	///
-	/// let mut map = BidirMap::new();
+	/// let mut map = SortedBidirMap::new();
 	/// map.insert(1, "a");
 	/// assert_eq!(map.remove_by_second(&"a"), Some((1, "a")));
 	/// assert_eq!(map.remove_by_second(&"b"), None);
 	/// ```
-	pub fn remove_by_second<Q: ?Sized>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+	pub fn remove_by_second<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
 		where Kv2: Borrow<Q>,
-		      Q  : PartialEq<Kv2>,
 	{
-		self.cont.iter().position(|ref kvs| *key == kvs.1).map(|idx| self.cont.swap_remove(idx))
+		let second_pos = self.by_second.binary_search_by(|&i| self.cont[i].1.borrow().cmp(key)).ok()?;
+		let slot = self.by_second.remove(second_pos);
+		let first_pos = self.by_first.binary_search_by(|&i| self.cont[i].0.cmp(&self.cont[slot].0)).expect("by_first out of sync with by_second");
+		self.by_first.remove(first_pos);
+		Some(self.finish_remove(slot))
 	}
-}
 
+	/// Finishes a removal: `cont[slot]` has already had its index dropped from both `by_first` and `by_second`,
+	/// so all that's left is to `swap_remove()` it out of `cont` and, since that moves the last element into
+	/// `slot`, fix up the single index array entry that pointed at it.
+	fn finish_remove(&mut self, slot: usize) -> (Kv1, Kv2) {
+		let removed = self.cont.swap_remove(slot);
+
+		let moved_from = self.cont.len();
+		if slot != moved_from {
+			if let Some(i) = self.by_first.iter_mut().find(|i| **i == moved_from) {
+				*i = slot;
+			}
+			if let Some(i) = self.by_second.iter_mut().find(|i| **i == moved_from) {
+				*i = slot;
+			}
+		}
+
+		removed
+	}
+}
 
 impl<Kv1: PartialEq, Kv2: PartialEq> IntoIterator for BidirMap<Kv1, Kv2> {
 	type Item = (Kv1, Kv2);
@@ -514,6 +1476,62 @@ impl<'a, 'q, Kv1: PartialEq, Kv2: PartialEq, Q: ?Sized + 'q> Index<&'a BySecond<
 	}
 }
 
+/// # Examples
+///
+/// ```
+/// use bidir_map::{BidirMap, ByFirst};
+///
+/// let mut map = BidirMap::new();
+/// map.insert(1, "a");
+/// map[ByFirst(&1)] = "b";
+/// assert_eq!(map.get_by_first(&1), Some(&"b"));
+/// ```
+impl<'q, Kv1: PartialEq, Kv2: PartialEq, Q: ?Sized + 'q> IndexMut<ByFirst<'q, Q>> for BidirMap<Kv1, Kv2>
+	where Kv1: Borrow<Q>,
+	      Q  : PartialEq<Kv1>,
+{
+	fn index_mut(&mut self, key: ByFirst<Q>) -> &mut Self::Output {
+		self.get_mut_by_first(&key.0).expect("no entry found for first key/value")
+	}
+}
+
+impl<'a, 'q, Kv1: PartialEq, Kv2: PartialEq, Q: ?Sized + 'q> IndexMut<&'a ByFirst<'q, Q>> for BidirMap<Kv1, Kv2>
+	where Kv1: Borrow<Q>,
+	      Q  : PartialEq<Kv1>,
+{
+	fn index_mut(&mut self, key: &ByFirst<Q>) -> &mut Self::Output {
+		self.get_mut_by_first(&key.0).expect("no entry found for first key/value")
+	}
+}
+
+/// # Examples
+///
+/// ```
+/// use bidir_map::{BidirMap, BySecond};
+///
+/// let mut map = BidirMap::new();
+/// map.insert(1, "a");
+/// map[BySecond(&"a")] = 2;
+/// assert_eq!(map.get_by_second(&"a"), Some(&2));
+/// ```
+impl<'q, Kv1: PartialEq, Kv2: PartialEq, Q: ?Sized + 'q> IndexMut<BySecond<'q, Q>> for BidirMap<Kv1, Kv2>
+	where Kv2: Borrow<Q>,
+	      Q  : PartialEq<Kv2>,
+{
+	fn index_mut(&mut self, key: BySecond<Q>) -> &mut Self::Output {
+		self.get_mut_by_second(&key.0).expect("no entry found for second key/value")
+	}
+}
+
+impl<'a, 'q, Kv1: PartialEq, Kv2: PartialEq, Q: ?Sized + 'q> IndexMut<&'a BySecond<'q, Q>> for BidirMap<Kv1, Kv2>
+	where Kv2: Borrow<Q>,
+	      Q  : PartialEq<Kv2>,
+{
+	fn index_mut(&mut self, key: &BySecond<Q>) -> &mut Self::Output {
+		self.get_mut_by_second(&key.0).expect("no entry found for second key/value")
+	}
+}
+
 
 /// An iterator over the K/V pairs contained in a `BidirMap`.
 ///
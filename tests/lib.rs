@@ -1,6 +1,6 @@
 extern crate bidir_map;
 
-use bidir_map::{BidirMap, ByFirst, BySecond};
+use bidir_map::{BidirMap, ByFirst, BySecond, SortedBidirMap};
 
 /// https://github.com/nabijaczleweli/bidir-map-rs/issues/1
 ///
@@ -43,3 +43,40 @@ fn nonexistant_ref_index_by_second_panics() {
 	map.insert("asdf".to_string(), 1234);
 	let _ = map[&BySecond(&4321)];
 }
+
+/// https://github.com/nabijaczleweli/bidir-map-rs/issues/2
+///
+/// `insert()` used to only resolve a collision on one side (mirroring `BidirMap`'s if/else), so a new pair
+/// whose first collided with one entry and whose second collided with a *different* entry left the latter's
+/// index behind in `by_second`, desyncing it from `cont` and panicking on the next lookup that touched it.
+#[test]
+fn sorted_insert_resolves_collisions_on_both_sides() {
+	let mut map = SortedBidirMap::new();
+	map.insert(30, 10);
+	map.insert(47, 49);
+	map.insert(47, 10);
+
+	assert_eq!(map.len(), 1);
+	assert_eq!(map.get_by_first(&47), Some(&10));
+	assert_eq!(map.get_by_second(&10), Some(&47));
+	assert_eq!(map.get_by_first(&30), None);
+	assert_eq!(map.get_by_second(&49), None);
+}
+
+/// After a double-sided collision, every surviving pair must still be reachable from *both* directions -- i.e.
+/// `by_first` and `by_second` must still agree with `cont`, rather than one of them holding on to a stale index
+/// of a pair `insert()` silently failed to evict.
+#[test]
+fn sorted_insert_keeps_indices_in_sync_after_double_collision() {
+	let mut map = SortedBidirMap::new();
+	map.insert(30, 10);
+	map.insert(47, 49);
+	map.insert(47, 10);
+	map.insert(5, 6);
+
+	assert_eq!(map.len(), 2);
+	for &(first, second) in &[(47, 10), (5, 6)] {
+		assert_eq!(map.get_by_first(&first), Some(&second));
+		assert_eq!(map.get_by_second(&second), Some(&first));
+	}
+}